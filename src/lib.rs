@@ -1,26 +1,54 @@
 #![feature(test)]
+#![feature(allocator_api)]
 
 use std::{
-    alloc::{alloc, realloc, Layout},
-    ops::{Deref, DerefMut},
+    alloc::{AllocError, Allocator, Global, Layout},
+    mem::MaybeUninit,
+    ops::{Bound, Deref, DerefMut, Index, IndexMut, RangeBounds},
+    ptr::NonNull,
 };
 
 pub(crate) const INCREMENTAL_CAPACITY: usize = 1024;
 pub(crate) const INITIAL_CAPACITY: usize = INCREMENTAL_CAPACITY * 2;
 
+/// A `DArray` backed by a fixed-capacity region has no heap to fall back on:
+/// once neither end has room and the region can't grow, pushes/inserts
+/// report this instead of panicking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Full;
+
+/// A double-ended array, growable from either end in amortized O(1).
+///
+/// `T` must be `Copy`: `push_start`/`insert`/`remove` and recentering all
+/// shift elements with a raw memmove (`shift_left`/`shift_right`/`move_to`),
+/// which duplicates the moved bytes without running `Drop` on the original.
+/// A non-`Copy`, `Drop`-owning `T` (e.g. `String`) would end up double-dropped.
 #[derive(Debug)]
-pub(crate) struct DArray {
-    array: DSlice,
+pub(crate) struct DArray<
+    T,
+    const INIT: usize = INITIAL_CAPACITY,
+    const STEP: usize = INCREMENTAL_CAPACITY,
+    A: Allocator = Global,
+> {
+    array: DSlice<T, STEP, A>,
     begin: usize,
     end: usize,
 }
 
-impl DArray {
+impl<T: Copy, const INIT: usize, const STEP: usize, A: Allocator + Default>
+    DArray<T, INIT, STEP, A>
+{
     pub(crate) fn new() -> Self {
+        Self::new_in(A::default())
+    }
+}
+
+impl<T: Copy, const INIT: usize, const STEP: usize, A: Allocator> DArray<T, INIT, STEP, A> {
+    pub(crate) fn new_in(allocator: A) -> Self {
         DArray {
-            array: DSlice::new(),
-            begin: INITIAL_CAPACITY / 2,
-            end: INITIAL_CAPACITY / 2,
+            array: DSlice::new_in(INIT, allocator),
+            begin: INIT / 2,
+            end: INIT / 2,
         }
     }
 
@@ -32,41 +60,157 @@ impl DArray {
         self.begin + index
     }
 
-    pub(crate) fn get(&mut self, index: usize) -> u64 {
-        let len = self.end - self.begin;
-        if index >= len {
-            panic!("Tried to index outside the array");
+    /// The live elements as a contiguous slice.
+    pub(crate) fn as_slice(&self) -> &[T] {
+        &self.array[self.begin..self.end]
+    }
+
+    /// The live elements as a contiguous mutable slice.
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.array[self.begin..self.end]
+    }
+
+    pub(crate) fn get(&self, index: usize) -> Option<&T> {
+        self.as_slice().get(index)
+    }
+
+    pub(crate) fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.as_mut_slice().get_mut(index)
+    }
+
+    pub(crate) fn front(&self) -> Option<&T> {
+        self.as_slice().first()
+    }
+
+    pub(crate) fn back(&self) -> Option<&T> {
+        self.as_slice().last()
+    }
+
+    pub(crate) fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+
+    pub(crate) fn pop_start(&mut self) -> Option<T> {
+        if self.begin == self.end {
+            return None;
         }
 
-        let index = self.map_index(index);
-        unsafe { *self.array.get_unchecked(index) }
+        let value = unsafe { *self.array.get_unchecked(self.begin) };
+        self.begin += 1;
+        Some(value)
+    }
+
+    pub(crate) fn pop_end(&mut self) -> Option<T> {
+        if self.begin == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        Some(unsafe { *self.array.get_unchecked(self.end) })
     }
 
-    pub(crate) fn push_end(&mut self, value: u64) {
-        let index = self.end;
-        if index == self.array.len() {
-            self.array.grow();
+    /// Removes the elements in `range`, returning them as an iterator. Any
+    /// elements not consumed from the iterator are still removed when it is
+    /// dropped.
+    pub(crate) fn drain(&mut self, range: impl RangeBounds<usize>) -> Drain<'_, T, INIT, STEP, A> {
+        let len = self.len();
+
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n + 1,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => len,
+        };
+
+        assert!(start <= end && end <= len, "Drain range outside the array");
+
+        let start = self.map_index(start);
+        let end = self.map_index(end);
+
+        Drain {
+            darray: self,
+            start,
+            cursor: start,
+            end,
+        }
+    }
+
+    pub(crate) fn push_end(&mut self, value: T) -> Result<(), Full> {
+        if self.end == self.array.len() {
+            self.make_room(false)?;
         }
 
         unsafe {
-            *self.array.get_unchecked_mut(index) = value;
+            *self.array.get_unchecked_mut(self.end) = value;
         }
 
         self.end += 1;
+        Ok(())
     }
 
-    pub(crate) fn push_start(&mut self, value: u64) {
+    pub(crate) fn push_start(&mut self, value: T) -> Result<(), Full> {
         if self.begin == 0 {
-            self.array.grow();
-            self.array.shift_right(0, self.end, INCREMENTAL_CAPACITY);
-            self.begin = INCREMENTAL_CAPACITY;
-            self.end += INCREMENTAL_CAPACITY;
+            self.make_room(true)?;
         }
 
         self.begin -= 1;
         unsafe {
             *self.array.get_unchecked_mut(self.begin) = value;
         }
+        Ok(())
+    }
+
+    /// Makes room at whichever end is currently exhausted.
+    ///
+    /// If there is enough free space overall, the live region is just
+    /// recentered in the existing allocation (an O(len) memmove); this is
+    /// what keeps alternating `push_start`/`push_end` calls amortized O(1)
+    /// instead of re-growing (and re-copying) on every single front push.
+    /// Only when free space is scarce do we try to grow the backing
+    /// allocation before recentering. A fixed-capacity allocator (e.g. a
+    /// `Region`) can't grow at all, so as long as some slack remains we fall
+    /// back to recentering with what's there; only a fully-packed array with
+    /// a non-growing allocator reports `Full`.
+    ///
+    /// `grow_front` says which end triggered the call: an even `free / 2`
+    /// split can round the exhausted side down to zero slack when `free` is
+    /// odd, which would immediately underflow the caller's `begin -= 1` (or
+    /// leave `push_end` with nowhere to write). Rounding the split toward
+    /// the exhausted side guarantees it gets at least one free slot.
+    fn make_room(&mut self, grow_front: bool) -> Result<(), Full> {
+        let len = self.end - self.begin;
+        let free = self.array.len() - len;
+
+        if free <= STEP && self.array.grow().is_err() && free == 0 {
+            return Err(Full);
+        }
+
+        self.recenter(len, grow_front);
+        Ok(())
+    }
+
+    fn recenter(&mut self, len: usize, grow_front: bool) {
+        let free = self.array.len() - len;
+        let new_begin = if grow_front {
+            free.div_ceil(2)
+        } else {
+            free / 2
+        };
+
+        if new_begin != self.begin {
+            self.array.move_to(self.begin, len, new_begin);
+        }
+
+        self.begin = new_begin;
+        self.end = new_begin + len;
     }
 
     pub(crate) fn remove(&mut self, index: usize) {
@@ -75,7 +219,7 @@ impl DArray {
         self.end -= 1;
     }
 
-    pub(crate) fn insert(&mut self, index: usize, value: u64) {
+    pub(crate) fn insert(&mut self, index: usize, value: T) -> Result<(), Full> {
         if index == 0 {
             return self.push_start(value);
         }
@@ -87,7 +231,7 @@ impl DArray {
         }
 
         if self.end == self.array.len() {
-            self.array.grow();
+            self.make_room(false)?;
         }
 
         let index = self.map_index(index);
@@ -97,88 +241,342 @@ impl DArray {
         }
 
         self.end = self.end + 1;
+        Ok(())
     }
 }
 
-#[derive(Debug)]
-pub(crate) struct DSlice(Box<[u64]>);
+impl<T: Copy, const INIT: usize, const STEP: usize, A: Allocator> Index<usize>
+    for DArray<T, INIT, STEP, A>
+{
+    type Output = T;
 
-impl DSlice {
-    pub(crate) fn new() -> Self {
-        let size = INITIAL_CAPACITY;
-        let layout = Layout::array::<u64>(size).expect("Invalid layout");
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("Tried to index outside the array")
+    }
+}
 
-        // Allocate the memory
-        let ptr: *mut u64 = unsafe { alloc(layout).cast() };
-        if ptr.is_null() {
-            panic!("Memory allocation failed");
-        }
+impl<T: Copy, const INIT: usize, const STEP: usize, A: Allocator> IndexMut<usize>
+    for DArray<T, INIT, STEP, A>
+{
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index)
+            .expect("Tried to index outside the array")
+    }
+}
 
-        DSlice(unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr, size) as *mut [u64]) })
+impl<'a, T: Copy, const INIT: usize, const STEP: usize, A: Allocator> IntoIterator
+    for &'a DArray<T, INIT, STEP, A>
+{
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: Copy, const INIT: usize, const STEP: usize, A: Allocator> IntoIterator
+    for &'a mut DArray<T, INIT, STEP, A>
+{
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
     }
+}
 
-    pub(crate) fn grow(&mut self) {
-        let size = self.0.len() + INCREMENTAL_CAPACITY;
-        let size_in_bytes = size * 8; // 64 bits = 8 bytes
-        let layout = Layout::array::<u64>(self.0.len()).expect("Invalid layout");
+/// Iterator returned by [`DArray::drain`]. Yields the removed elements; on
+/// drop, closes the gap they left behind with a single memmove.
+pub(crate) struct Drain<'a, T, const INIT: usize, const STEP: usize, A: Allocator> {
+    darray: &'a mut DArray<T, INIT, STEP, A>,
+    start: usize,
+    cursor: usize,
+    end: usize,
+}
 
-        // Reallocate same memory block.
-        let ptr: *mut u64 =
-            unsafe { realloc(self.0.as_mut_ptr().cast(), layout, size_in_bytes).cast() };
-        if ptr.is_null() {
-            panic!("Memory re-allocation failed");
+impl<'a, T: Copy, const INIT: usize, const STEP: usize, A: Allocator> Iterator
+    for Drain<'a, T, INIT, STEP, A>
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.cursor >= self.end {
+            return None;
         }
-        if !ptr.is_aligned() {
-            panic!("Memory re-allocation not aligned");
+
+        let value = unsafe { *self.darray.array.get_unchecked(self.cursor) };
+        self.cursor += 1;
+        Some(value)
+    }
+}
+
+impl<'a, T, const INIT: usize, const STEP: usize, A: Allocator> Drop
+    for Drain<'a, T, INIT, STEP, A>
+{
+    fn drop(&mut self) {
+        let removed = self.end - self.start;
+        let tail_len = self.darray.end - self.end;
+
+        if removed > 0 && tail_len > 0 {
+            self.darray.array.move_to(self.end, tail_len, self.start);
         }
 
-        // Replace the old Box with a new one pointing to the new address.
-        let old_box = std::mem::replace(&mut self.0, unsafe {
-            Box::from_raw(std::slice::from_raw_parts_mut(ptr, size) as *mut [u64])
-        });
+        self.darray.end -= removed;
+    }
+}
+
+/// Whether a `DSlice::grow` extended the existing allocation in place or had
+/// to relocate it to a new address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GrowOutcome {
+    GrewInPlace,
+    Relocated,
+}
+
+#[derive(Debug)]
+pub(crate) struct DSlice<T, const STEP: usize = INCREMENTAL_CAPACITY, A: Allocator = Global> {
+    ptr: std::ptr::NonNull<T>,
+    len: usize,
+    allocator: A,
+}
+
+impl<T, const STEP: usize, A: Allocator + Default> DSlice<T, STEP, A> {
+    pub(crate) fn new(size: usize) -> Self {
+        Self::new_in(size, A::default())
+    }
+}
+
+impl<T, const STEP: usize, A: Allocator> DSlice<T, STEP, A> {
+    pub(crate) fn new_in(size: usize, allocator: A) -> Self {
+        let layout = Layout::array::<T>(size).expect("Invalid layout");
+
+        let ptr = allocator
+            .allocate(layout)
+            .expect("Memory allocation failed")
+            .cast::<T>();
+
+        DSlice {
+            ptr,
+            len: size,
+            allocator,
+        }
+    }
 
-        // Leak the old box, so it doesn't try to drop the now invalid address it used to point to.
-        Box::leak(old_box);
+    /// Grows the backing allocation by `STEP` elements.
+    ///
+    /// `Allocator::grow` is allowed to extend the block in place when the
+    /// allocator has trailing room for it, in which case no bytes need to be
+    /// moved at all. Reports which of the two happened, in case a future
+    /// caller that caches a pointer derived from the base address needs to
+    /// know whether to re-derive it; `DArray` addresses by index rather than
+    /// by pointer, so nothing in this crate branches on the outcome today.
+    /// Fails when the allocator can't satisfy the larger layout at all,
+    /// e.g. a fixed-capacity `Region` that has run out of backing memory.
+    pub(crate) fn grow(&mut self) -> Result<GrowOutcome, AllocError> {
+        let old_layout = Layout::array::<T>(self.len).expect("Invalid layout");
+        let new_len = self.len + STEP;
+        let new_layout = Layout::array::<T>(new_len).expect("Invalid layout");
+        let old_ptr = self.ptr;
+
+        // Reallocate through the allocator, which also takes care of freeing
+        // the old block if the memory has to move.
+        let ptr = unsafe {
+            self.allocator
+                .grow(self.ptr.cast(), old_layout, new_layout)?
+        };
+
+        self.ptr = ptr.cast();
+        self.len = new_len;
+
+        if self.ptr == old_ptr {
+            Ok(GrowOutcome::GrewInPlace)
+        } else {
+            Ok(GrowOutcome::Relocated)
+        }
     }
 
     pub(crate) fn shift_right(&mut self, offset: usize, count: usize, shift_amount: usize) {
         unsafe {
-            let ptr = self.0.as_mut_ptr().offset(offset as isize);
+            let ptr = self.as_mut_ptr().offset(offset as isize);
             let dest = ptr.offset(shift_amount as isize);
-            std::intrinsics::copy(ptr as *const u64, dest, count);
+            std::intrinsics::copy(ptr as *const T, dest, count);
         }
     }
 
     pub(crate) fn shift_left(&mut self, offset: usize, count: usize) {
         unsafe {
-            let ptr = self.0.as_mut_ptr().offset((offset + 1) as isize);
+            let ptr = self.as_mut_ptr().offset((offset + 1) as isize);
             let dest = ptr.offset(-1);
-            std::intrinsics::copy(ptr as *const u64, dest, count);
+            std::intrinsics::copy(ptr as *const T, dest, count);
+        }
+    }
+
+    /// Moves `count` elements starting at `from` to start at `to`, in either
+    /// direction. Unlike `shift_right`/`shift_left`, which only ever shift by
+    /// a fixed amount relative to their caller's layout, this takes an
+    /// absolute destination, which is what recentering needs.
+    pub(crate) fn move_to(&mut self, from: usize, count: usize, to: usize) {
+        unsafe {
+            let src = self.as_mut_ptr().add(from);
+            let dest = self.as_mut_ptr().add(to);
+            std::intrinsics::copy(src as *const T, dest, count);
         }
     }
 }
 
-impl DerefMut for DSlice {
+impl<T, const STEP: usize, A: Allocator> Drop for DSlice<T, STEP, A> {
+    fn drop(&mut self) {
+        let layout = Layout::array::<T>(self.len).expect("Invalid layout");
+        unsafe { self.allocator.deallocate(self.ptr.cast(), layout) };
+    }
+}
+
+impl<T, const STEP: usize, A: Allocator> DerefMut for DSlice<T, STEP, A> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        self.0.deref_mut()
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
     }
 }
 
-impl Deref for DSlice {
-    type Target = [u64];
+impl<T, const STEP: usize, A: Allocator> Deref for DSlice<T, STEP, A> {
+    type Target = [T];
 
     fn deref(&self) -> &Self::Target {
-        self.0.deref()
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+/// A single-allocation, no-heap `Allocator` over a caller-supplied memory
+/// region, modeled on `alloc-compose`'s stack `Region`. A `DArray` backed by
+/// a `Region` never touches the global allocator: `allocate` hands out the
+/// start of the region, and `grow` extends in place as long as the region is
+/// big enough, or fails once it isn't, giving callers a bounded,
+/// deterministic double-ended array backed by memory they own. This crate
+/// still depends on `std` throughout (this type included), so it isn't
+/// `no_std`-ready on its own; that would also require routing `DArray`/
+/// `DSlice` through `core`/`alloc` instead. Tracked as follow-up work, not
+/// attempted here.
+pub(crate) struct Region<'a> {
+    buf: std::cell::UnsafeCell<&'a mut [MaybeUninit<u8>]>,
+}
+
+impl<'a> Region<'a> {
+    pub(crate) fn new(buf: &'a mut [MaybeUninit<u8>]) -> Self {
+        Region {
+            buf: std::cell::UnsafeCell::new(buf),
+        }
+    }
+
+    /// Builds a `Region` over an inline, typed buffer (e.g. a `[MaybeUninit<T>; N]`
+    /// backing array) by reinterpreting it as raw bytes.
+    pub(crate) fn from_uninit_slice<T>(buf: &'a mut [MaybeUninit<T>]) -> Self {
+        let len = std::mem::size_of_val(buf);
+        let ptr = buf.as_mut_ptr().cast::<MaybeUninit<u8>>();
+        Region::new(unsafe { std::slice::from_raw_parts_mut(ptr, len) })
+    }
+}
+
+unsafe impl<'a> Allocator for Region<'a> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let buf = unsafe { &mut *self.buf.get() };
+        if layout.size() > buf.len() || !(buf.as_ptr() as usize).is_multiple_of(layout.align()) {
+            return Err(AllocError);
+        }
+
+        let ptr = NonNull::new(buf.as_mut_ptr().cast::<u8>()).ok_or(AllocError)?;
+        Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+    }
+
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {
+        // The region is owned by the caller, not by us; nothing to free.
+    }
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        _old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let buf = unsafe { &mut *self.buf.get() };
+        if new_layout.size() > buf.len() {
+            return Err(AllocError);
+        }
+
+        // The region only ever serves one allocation at its base address,
+        // so growing never has to move anything.
+        Ok(NonNull::slice_from_raw_parts(ptr, new_layout.size()))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::{cell::Cell, rc::Rc};
+
+    /// Wraps `Global`, counting allocate/deallocate calls and always
+    /// relocating on `grow` (allocate new, copy, free old), so tests can
+    /// assert every allocation is eventually matched by a deallocation.
+    #[derive(Clone, Default)]
+    struct CountingAllocator {
+        allocations: Rc<Cell<usize>>,
+        deallocations: Rc<Cell<usize>>,
+    }
+
+    unsafe impl Allocator for CountingAllocator {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            let ptr = Global.allocate(layout)?;
+            self.allocations.set(self.allocations.get() + 1);
+            Ok(ptr)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            unsafe { Global.deallocate(ptr, layout) };
+            self.deallocations.set(self.deallocations.get() + 1);
+        }
+
+        unsafe fn grow(
+            &self,
+            ptr: NonNull<u8>,
+            old_layout: Layout,
+            new_layout: Layout,
+        ) -> Result<NonNull<[u8]>, AllocError> {
+            let new_ptr = Global.allocate(new_layout)?;
+            self.allocations.set(self.allocations.get() + 1);
+            unsafe {
+                std::ptr::copy_nonoverlapping(
+                    ptr.as_ptr(),
+                    new_ptr.as_ptr().cast(),
+                    old_layout.size(),
+                );
+                Global.deallocate(ptr, old_layout);
+            }
+            self.deallocations.set(self.deallocations.get() + 1);
+            Ok(new_ptr)
+        }
+    }
+
+    #[test]
+    fn test_dslice_grow_and_drop_do_not_leak() {
+        let allocations = Rc::new(Cell::new(0));
+        let deallocations = Rc::new(Cell::new(0));
+        let allocator = CountingAllocator {
+            allocations: allocations.clone(),
+            deallocations: deallocations.clone(),
+        };
+
+        {
+            let mut dslice: DSlice<u64, 4, CountingAllocator> = DSlice::new_in(4, allocator);
+            dslice.grow().unwrap();
+            dslice.grow().unwrap();
+        }
+
+        assert_eq!(allocations.get(), 3);
+        assert_eq!(deallocations.get(), 3);
+    }
 
     #[test]
     fn it_works() {
-        let mut dslice = DSlice::new();
+        let mut dslice: DSlice<u64> = DSlice::new(INITIAL_CAPACITY);
         assert_eq!(dslice.len(), INITIAL_CAPACITY);
 
         let _ = &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]
@@ -190,7 +588,7 @@ mod tests {
         assert_eq!(dslice.get(9), Some(&10));
         assert_eq!(dslice.get(10), Some(&0));
 
-        dslice.grow();
+        dslice.grow().unwrap();
         assert_eq!(dslice.len(), INITIAL_CAPACITY + INCREMENTAL_CAPACITY);
 
         dslice.shift_right(4, 6, 1);
@@ -202,69 +600,171 @@ mod tests {
         assert_eq!(dslice.get(11), Some(&0));
     }
 
+    #[test]
+    fn test_dslice_grow_with_differently_sized_type() {
+        // u8 has a different size and alignment than the u64 every other
+        // test uses, exercising the Layout::array::<T> size math directly.
+        let mut dslice: DSlice<u8, 4> = DSlice::new(4);
+        assert_eq!(dslice.len(), 4);
+
+        dslice.grow().unwrap();
+        assert_eq!(dslice.len(), 8);
+    }
+
+    #[test]
+    fn test_darray_generic_over_differently_sized_type() {
+        let mut darray: DArray<u8, 2, 4> = DArray::new();
+
+        for i in 0..5u8 {
+            darray.push_end(i).unwrap();
+        }
+        assert_eq!(darray.as_slice(), &[0, 1, 2, 3, 4]);
+
+        darray.insert(2, 99).unwrap();
+        assert_eq!(darray.as_slice(), &[0, 1, 99, 2, 3, 4]);
+
+        darray.push_start(200).unwrap();
+        assert_eq!(darray.as_slice(), &[200, 0, 1, 99, 2, 3, 4]);
+
+        darray.pop_end();
+        darray.pop_start();
+        assert_eq!(darray.as_slice(), &[0, 1, 99, 2, 3]);
+    }
+
+    #[test]
+    fn test_dslice_grow_in_place_with_region() {
+        let mut buf = [MaybeUninit::<u64>::uninit(); 8];
+        let region = Region::from_uninit_slice(&mut buf);
+        let mut dslice: DSlice<u64, 4, Region> = DSlice::new_in(4, region);
+
+        assert_eq!(dslice.grow().unwrap(), GrowOutcome::GrewInPlace);
+        assert_eq!(dslice.len(), 8);
+    }
+
     #[test]
     fn test_darray() {
-        let mut darray = DArray::new();
+        let mut darray: DArray<u64> = DArray::new();
         for i in 1..=10 {
-            darray.push_end(i);
+            darray.push_end(i).unwrap();
         }
 
-        assert_eq!(darray.get(0), 1);
-        assert_eq!(darray.get(9), 10);
+        assert_eq!(darray[0], 1);
+        assert_eq!(darray[9], 10);
 
-        darray.insert(4, 42);
+        darray.insert(4, 42).unwrap();
 
-        assert_eq!(darray.get(4), 42);
-        assert_eq!(darray.get(5), 5);
-        assert_eq!(darray.get(10), 10);
+        assert_eq!(darray[4], 42);
+        assert_eq!(darray[5], 5);
+        assert_eq!(darray[10], 10);
 
-        darray.insert(0, 42);
+        darray.insert(0, 42).unwrap();
 
-        assert_eq!(darray.get(0), 42);
-        assert_eq!(darray.get(5), 42);
-        assert_eq!(darray.get(11), 10);
+        assert_eq!(darray[0], 42);
+        assert_eq!(darray[5], 42);
+        assert_eq!(darray[11], 10);
 
-        darray.push_start(42);
+        darray.push_start(42).unwrap();
 
-        assert_eq!(darray.get(0), 42);
-        assert_eq!(darray.get(1), 42);
-        assert_eq!(darray.get(6), 42);
-        assert_eq!(darray.get(12), 10);
+        assert_eq!(darray[0], 42);
+        assert_eq!(darray[1], 42);
+        assert_eq!(darray[6], 42);
+        assert_eq!(darray[12], 10);
 
         darray.remove(0);
 
-        assert_eq!(darray.get(0), 42);
-        assert_eq!(darray.get(5), 42);
-        assert_eq!(darray.get(11), 10);
+        assert_eq!(darray[0], 42);
+        assert_eq!(darray[5], 42);
+        assert_eq!(darray[11], 10);
 
         darray.remove(11);
 
-        assert_eq!(darray.get(0), 42);
-        assert_eq!(darray.get(5), 42);
+        assert_eq!(darray[0], 42);
+        assert_eq!(darray[5], 42);
+    }
+
+    #[test]
+    fn test_darray_region_backed() {
+        let mut buf = [MaybeUninit::<u64>::uninit(); 8];
+        let region = Region::from_uninit_slice(&mut buf);
+        let mut darray: DArray<u64, 8, 4, Region> = DArray::new_in(region);
+
+        for i in 0..8 {
+            darray.push_end(i).unwrap();
+        }
+
+        assert_eq!(darray.push_end(8), Err(Full));
+        assert_eq!(darray[0], 0);
+        assert_eq!(darray[7], 7);
+    }
+
+    #[test]
+    fn test_darray_push_start_odd_free_space_does_not_underflow() {
+        let mut buf = [MaybeUninit::<u64>::uninit(); 3];
+        let region = Region::from_uninit_slice(&mut buf);
+        let mut darray: DArray<u64, 3, 4, Region> = DArray::new_in(region);
+
+        darray.push_end(1).unwrap();
+        darray.push_start(2).unwrap();
+        darray.push_start(3).unwrap();
+
+        assert_eq!(darray.as_slice(), &[3, 2, 1]);
+    }
+
+    #[test]
+    fn test_darray_views() {
+        let mut darray: DArray<u64> = DArray::new();
+        for i in 1..=5 {
+            darray.push_end(i).unwrap();
+        }
+
+        assert_eq!(darray.as_slice(), &[1, 2, 3, 4, 5]);
+        assert_eq!(darray.front(), Some(&1));
+        assert_eq!(darray.back(), Some(&5));
+        assert_eq!(darray.iter().sum::<u64>(), 15);
+
+        for value in darray.iter_mut() {
+            *value *= 10;
+        }
+        assert_eq!(darray.as_slice(), &[10, 20, 30, 40, 50]);
+
+        let drained: Vec<u64> = darray.drain(1..3).collect();
+        assert_eq!(drained, vec![20, 30]);
+        assert_eq!(darray.as_slice(), &[10, 40, 50]);
+
+        assert_eq!(darray.pop_start(), Some(10));
+        assert_eq!(darray.pop_end(), Some(50));
+        assert_eq!(darray.as_slice(), &[40]);
+
+        let sum: u64 = (&darray).into_iter().sum();
+        assert_eq!(sum, 40);
+
+        darray.pop_start();
+        assert_eq!(darray.pop_start(), None);
+        assert_eq!(darray.pop_end(), None);
     }
 
     #[test]
     #[should_panic]
     fn test_darray_out_of_bounds() {
-        let mut darray = DArray::new();
+        let mut darray: DArray<u64> = DArray::new();
         for i in 1..=10 {
-            darray.push_end(i);
+            darray.push_end(i).unwrap();
         }
 
-        darray.get(10);
+        let _ = darray[10];
     }
 
     #[test]
     #[should_panic]
     fn test_darray_remove_moves_bounds() {
-        let mut darray = DArray::new();
+        let mut darray: DArray<u64> = DArray::new();
         for i in 1..=10 {
-            darray.push_end(i);
+            darray.push_end(i).unwrap();
         }
 
         darray.remove(0);
 
-        darray.get(9);
+        let _ = darray[9];
     }
 
     extern crate test;
@@ -273,9 +773,9 @@ mod tests {
     #[bench]
     fn bench_prepends(b: &mut Bencher) {
         b.iter(|| {
-            let mut darray = DArray::new();
+            let mut darray: DArray<u64> = DArray::new();
             for i in 0..=200000 {
-                darray.push_start(i);
+                darray.push_start(i).unwrap();
             }
         })
     }
@@ -283,9 +783,9 @@ mod tests {
     #[bench]
     fn bench_appends(b: &mut Bencher) {
         b.iter(|| {
-            let mut darray = DArray::new();
+            let mut darray: DArray<u64> = DArray::new();
             for i in 0..=200000 {
-                darray.push_end(i);
+                darray.push_end(i).unwrap();
             }
         })
     }
@@ -293,9 +793,9 @@ mod tests {
     #[bench]
     fn bench_mid_inserts(b: &mut Bencher) {
         b.iter(|| {
-            let mut darray = DArray::new();
+            let mut darray: DArray<u64> = DArray::new();
             for i in 0..=2000 {
-                darray.insert(darray.len() / 2, i);
+                darray.insert(darray.len() / 2, i).unwrap();
             }
         })
     }